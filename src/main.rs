@@ -1,4 +1,3 @@
-use std::borrow::Cow;
 use std::collections::HashMap;
 use std::io::{Read, Write};
 use std::ops::RangeInclusive;
@@ -9,6 +8,9 @@ use regex::Regex;
 
 const EMOJI_SEQUENCES: &str = "emoji-sequences.txt";
 const EMOJI_ZWJ_SEQUENCES: &str = "emoji-zwj-sequences.txt";
+const ANNOTATIONS_EN: &str = "annotations/en.xml";
+const SHORTCODES: &str = "shortcodes.txt";
+const UNICODE_DATA: &str = "UnicodeData.txt";
 
 struct Emoji<'a> {
     pub sequence: Sequence,
@@ -16,7 +18,15 @@ struct Emoji<'a> {
 }
 
 enum Sequence {
-    Range(RangeInclusive<char>),
+    // A run of codepoints sharing a literal prefix/suffix, varying only in
+    // the `base` component, e.g. keycap sequences "<digit> FE0F 20E3" for
+    // digit in 0..=9, or the bare Fitzpatrick skin-tone modifiers
+    // themselves (prefix and suffix both empty).
+    Range {
+        prefix: String,
+        base: RangeInclusive<char>,
+        suffix: String,
+    },
     Literal(String),
 }
 
@@ -30,7 +40,7 @@ impl Scanner {
     pub fn new() -> Self {
         let line_re = Regex::new(r"^(.*?);(.*?);(.*?)#(.*?)$").unwrap();
         let range_re = Regex::new(r"^([A-F0-9]+)\.\.([A-F0-9]+)$").unwrap();
-        let lit_re = Regex::new(r"^[A-F0-9]+(\s+[A-F0-9])*$").unwrap();
+        let lit_re = Regex::new(r"^[A-F0-9]+(\s+[A-F0-9]+)*$").unwrap();
 
         Scanner {
             line_re,
@@ -56,16 +66,26 @@ impl Scanner {
     }
 
     fn scan_seq(&self, seq: &str) -> Option<Sequence> {
-        if let Some(range) = self.range_re.captures(seq) {
+        let tokens: Vec<&str> = seq.split_whitespace().collect();
+
+        // a range may appear as one token among others, e.g. the keycap
+        // sequences "0030..0039 FE0F 20E3" (digit 0..=9, each followed by
+        // the same two literal codepoints)
+        if let Some(range_idx) = tokens.iter().position(|t| self.range_re.is_match(t)) {
+            let range = self.range_re.captures(tokens[range_idx])?;
             let low = unichar(range.get(1)?.as_str())?;
             let high = unichar(range.get(2)?.as_str())?;
 
-            return Some(Sequence::Range(low..=high));
+            let prefix = tokens[..range_idx].iter().copied().map(unichar).collect::<Option<String>>()?;
+            let suffix = tokens[range_idx + 1..].iter().copied().map(unichar).collect::<Option<String>>()?;
+
+            return Some(Sequence::Range { prefix, base: low..=high, suffix });
         }
 
         if self.lit_re.is_match(seq) {
-            let lit = seq
-                .split_whitespace()
+            let lit = tokens
+                .iter()
+                .copied()
                 .map(unichar)
                 .collect::<Option<String>>()?;
 
@@ -80,12 +100,145 @@ impl Scanner {
     }
 }
 
+// Parses CLDR `annotations/<lang>.xml` files, which give pipe-separated
+// keywords for an emoji sequence, e.g.:
+//   <annotation cp="😀">face | grin | mean | smile</annotation>
+// Rows with `type="tts"` carry the spoken name instead of keywords, and are
+// handled separately (see `DataDir`'s locale support).
+struct AnnotationScanner {
+    keyword_re: Regex,
+    tts_re: Regex,
+}
+
+impl AnnotationScanner {
+    pub fn new() -> Self {
+        AnnotationScanner {
+            keyword_re: Regex::new(r#"<annotation cp="([^"]+)">([^<]*)</annotation>"#).unwrap(),
+            tts_re: Regex::new(r#"<annotation cp="([^"]+)" type="tts">([^<]*)</annotation>"#).unwrap(),
+        }
+    }
+
+    // Map emoji sequence -> search keywords, e.g. "😀" -> ["face", "grin", ...].
+    pub fn keywords(&self, text: &str) -> HashMap<String, Vec<String>> {
+        let mut out = HashMap::new();
+
+        for line in text.lines() {
+            if self.tts_re.is_match(line) {
+                continue;
+            }
+
+            if let Some(cap) = self.keyword_re.captures(line) {
+                let cp = cap[1].to_string();
+                let words = cap[2]
+                    .split('|')
+                    .map(|w| w.trim().to_string())
+                    .filter(|w| !w.is_empty())
+                    .collect();
+
+                out.insert(cp, words);
+            }
+        }
+
+        out
+    }
+
+    // Map emoji sequence -> localized spoken name, e.g. "😀" -> "grinning face".
+    // Used to pick a localized dmenu label when a `--lang`/`DMOJI_LANG` other
+    // than English is selected.
+    pub fn tts(&self, text: &str) -> HashMap<String, String> {
+        let mut out = HashMap::new();
+
+        for line in text.lines() {
+            if let Some(cap) = self.tts_re.captures(line) {
+                out.insert(cap[1].to_string(), cap[2].trim().to_string());
+            }
+        }
+
+        out
+    }
+}
+
+// Parses a GitHub-style shortcode table mapping an emoji sequence to its
+// `:shortcode:` aliases, one sequence per line:
+//   😀;grinning,grinning face
+struct ShortcodeScanner {
+    line_re: Regex,
+}
+
+impl ShortcodeScanner {
+    pub fn new() -> Self {
+        ShortcodeScanner {
+            line_re: Regex::new(r"^(.+?);(.+)$").unwrap(),
+        }
+    }
+
+    // Map emoji sequence -> shortcode aliases, e.g. "😀" -> [":grinning:", ...].
+    pub fn shortcodes(&self, text: &str) -> HashMap<String, Vec<String>> {
+        let mut out = HashMap::new();
+
+        for line in text.lines() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(cap) = self.line_re.captures(line) {
+                let seq = cap[1].to_string();
+                let aliases = cap[2]
+                    .split(',')
+                    .map(|a| format!(":{}:", a.trim()))
+                    .collect();
+
+                out.insert(seq, aliases);
+            }
+        }
+
+        out
+    }
+}
+
+// Parses `UnicodeData.txt`, the UCD's semicolon-delimited record of every
+// assigned codepoint, e.g. `2603;SNOWMAN;So;0;ON;;;;;N;;;;;`. Used to power
+// the `--unicode`/`-u` general character picker mode.
+struct UnicodeScanner;
+
+impl UnicodeScanner {
+    pub fn new() -> Self {
+        UnicodeScanner
+    }
+
+    pub fn entries<'a>(&'a self, text: &'a str) -> impl Iterator<Item = Emoji<'a>> + 'a {
+        text.lines().filter_map(move |line| self.scan_line(line))
+    }
+
+    fn scan_line<'a>(&self, line: &'a str) -> Option<Emoji<'a>> {
+        let mut fields = line.split(';');
+        let cp = fields.next()?;
+        let name = fields.next()?;
+
+        // skip unnamed/control entries and the `<..., First>`/`<..., Last>`
+        // range markers used for large blocks like CJK ideographs
+        if name.is_empty() || name.starts_with('<') {
+            return None;
+        }
+
+        let ch = std::char::from_u32(u32::from_str_radix(cp, 16).ok()?)?;
+
+        Some(Emoji {
+            sequence: Sequence::Literal(ch.to_string()),
+            description: name,
+        })
+    }
+}
+
 struct DataDir {
     path: PathBuf,
+    lang: String,
 }
 
 impl DataDir {
-    pub fn locate() -> Self {
+    pub fn locate(lang: String) -> Self {
         // debug_assertions is the officially ordained cfg var to test for debug
         // builds: https://stackoverflow.com/a/39205417
         if cfg!(debug_assertions) {
@@ -96,13 +249,13 @@ impl DataDir {
             exe.pop();
             exe.pop();
 
-            Self::new(exe)
+            Self::new(exe, lang)
         } else {
             if let Some(exe) = std::env::current_exe().ok() {
                 let path = exe.join("../share/dmoji");
 
                 if path.join(EMOJI_SEQUENCES).is_file() {
-                    return Self::new(path);
+                    return Self::new(path, lang);
                 }
             }
 
@@ -111,8 +264,8 @@ impl DataDir {
         }
     }
 
-    fn new(path: PathBuf) -> Self {
-        DataDir { path }
+    fn new(path: PathBuf, lang: String) -> Self {
+        DataDir { path, lang }
     }
 
     pub fn load_file(&self, file: &str) -> String {
@@ -126,11 +279,121 @@ impl DataDir {
             }
         }
     }
+
+    // Path (relative to the data dir) of the CLDR annotations file for the
+    // selected locale, e.g. "annotations/fr.xml".
+    pub fn locale_annotations_file(&self) -> String {
+        format!("annotations/{}.xml", self.lang)
+    }
+}
+
+// Score halves every this many days since an entry was last picked, so
+// yesterday's favorites still rank above never-used entries but stale picks
+// fade over time.
+const HISTORY_HALF_LIFE_DAYS: f64 = 14.0;
+
+struct HistoryEntry {
+    hits: u32,
+    last_used: u64,
+}
+
+// Tracks per-sequence usage (hit count + last-used time) in a small file
+// under the XDG state dir, so frequently/recently picked emoji float to the
+// top of the dmenu list instead of being ordered by arbitrary HashMap
+// iteration.
+struct History {
+    entries: HashMap<String, HistoryEntry>,
+}
+
+impl History {
+    pub fn load() -> Self {
+        let text = std::fs::read_to_string(Self::path()).unwrap_or_default();
+        let mut entries = HashMap::new();
+
+        for line in text.lines() {
+            let mut fields = line.splitn(3, ';');
+
+            let seq = fields.next();
+            let hits = fields.next().and_then(|s| s.parse().ok());
+            let last_used = fields.next().and_then(|s| s.parse().ok());
+
+            if let (Some(seq), Some(hits), Some(last_used)) = (seq, hits, last_used) {
+                entries.insert(seq.to_string(), HistoryEntry { hits, last_used });
+            }
+        }
+
+        History { entries }
+    }
+
+    // Frecency score for `seq`: hit count decayed by time since last use.
+    // Sequences with no history score 0, so the caller's own tie-break
+    // ordering (alphabetical) applies to them.
+    pub fn score(&self, seq: &str) -> f64 {
+        match self.entries.get(seq) {
+            Some(entry) => {
+                let elapsed_days = now_unix().saturating_sub(entry.last_used) as f64 / 86400.0;
+                let decay = 0.5f64.powf(elapsed_days / HISTORY_HALF_LIFE_DAYS);
+
+                entry.hits as f64 * decay
+            }
+            None => 0.0,
+        }
+    }
+
+    // Record a selection of `seq` and persist the updated history.
+    pub fn record(&mut self, seq: &str) {
+        let entry = self.entries.entry(seq.to_string())
+            .or_insert(HistoryEntry { hits: 0, last_used: 0 });
+
+        entry.hits += 1;
+        entry.last_used = now_unix();
+
+        self.save();
+    }
+
+    fn save(&self) {
+        let path = Self::path();
+
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+
+        let mut out = String::new();
+
+        for (seq, entry) in &self.entries {
+            out.push_str(&format!("{};{};{}\n", seq, entry.hits, entry.last_used));
+        }
+
+        let _ = std::fs::write(path, out);
+    }
+
+    fn path() -> PathBuf {
+        if let Some(dir) = std::env::var_os("XDG_STATE_HOME") {
+            return PathBuf::from(dir).join("dmoji/history");
+        }
+
+        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+        PathBuf::from(home).join(".local/state/dmoji/history")
+    }
+}
+
+fn now_unix() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
 }
 
 fn main() {
+    // resolve and validate the output backend up front, before dmenu is
+    // even spawned, so a bad --output/DMOJI_OUTPUT value fails fast instead
+    // of only surfacing after the user has already picked an entry
+    let output = Output::resolve();
+    let primary = has_flag("--primary", "-p");
+
     let scanner = Scanner::new();
-    let data = DataDir::locate();
+    let lang = resolve_lang();
+    let data = DataDir::locate(lang.clone());
 
     // load emoji from data files
     let pri_emoji = data.load_file(EMOJI_SEQUENCES);
@@ -139,23 +402,105 @@ fn main() {
     let emoji = scanner.emoji(&pri_emoji)
         .chain(scanner.emoji(&zwj_emoji));
 
-    // construct map of description -> emoji
+    // load keyword/shortcode search data so plain-language and :shortcode:
+    // queries can find a sequence, not just its terse Unicode description
+    let annotations = data.load_file(ANNOTATIONS_EN);
+    let shortcodes = data.load_file(SHORTCODES);
+
+    let keywords = AnnotationScanner::new().keywords(&annotations);
+    let shortcodes = ShortcodeScanner::new().shortcodes(&shortcodes);
+
+    // load localized names for the selected locale, falling back to the
+    // canonical English description when a sequence has no translation
+    let localized_names = if lang == "en" {
+        HashMap::new()
+    } else {
+        let text = data.load_file(&data.locale_annotations_file());
+        AnnotationScanner::new().tts(&text)
+    };
+
+    // whether to show every skin-tone variant of a modifiable emoji, or
+    // collapse them down to just the default (no-modifier) glyph
+    let collapse_skin_tones = collapse_skin_tones();
+
+    // construct map of description (+ search keywords) -> emoji
     let mut map = HashMap::new();
 
     for em in emoji {
         match em.sequence {
             Sequence::Literal(seq) => {
-                map.insert(Cow::Borrowed(em.description), seq);
+                if collapse_skin_tones && seq.chars().any(is_skin_tone_modifier) {
+                    continue;
+                }
+
+                let descr = localized_names.get(&seq).map(String::as_str).unwrap_or(em.description);
+                let label = search_label(descr, &seq, &keywords, &shortcodes);
+                map.insert(label, seq);
             }
-            Sequence::Range(chars) => {
-                for (idx, ch) in chars.enumerate() {
-                    let name = format!("{}-{}", em.description, idx);
-                    map.insert(Cow::Owned(name), ch.to_string());
+            Sequence::Range { prefix, base, suffix } => {
+                // the bare Fitzpatrick modifiers themselves: not meaningful
+                // as standalone selections, so give them proper tone names
+                // instead of "emoji modifier-3", or drop them entirely when
+                // collapsing skin-tone variants
+                if prefix.is_empty() && suffix.is_empty()
+                    && is_skin_tone_modifier(*base.start()) && is_skin_tone_modifier(*base.end())
+                {
+                    if !collapse_skin_tones {
+                        for (idx, ch) in base.enumerate() {
+                            let seq = ch.to_string();
+                            let tone = FITZPATRICK_TONES.get(idx).copied().unwrap_or("skin tone");
+                            let label = localized_names.get(&seq).cloned()
+                                .unwrap_or_else(|| format!("skin tone modifier: {}", tone));
+                            map.insert(label, seq);
+                        }
+                    }
+
+                    continue;
+                }
+
+                // a templated range, e.g. keycap sequences: reuse the
+                // shared part of the low/high descriptions ("keycap: ") as
+                // the label prefix for each element, falling back to the
+                // canonical English description when a given element has
+                // no translation of its own
+                for (idx, ch) in base.enumerate() {
+                    let seq = format!("{}{}{}", prefix, ch, suffix);
+                    let descr = match localized_names.get(&seq) {
+                        Some(descr) => descr.clone(),
+                        None => match range_desc_template(em.description) {
+                            Some(t) => format!("{}{}", t, ch),
+                            None => format!("{}-{}", em.description, idx),
+                        }
+                    };
+                    let label = search_label(&descr, &seq, &keywords, &shortcodes);
+                    map.insert(label, seq);
                 }
             }
         }
     }
 
+    // --unicode/-u: also offer every named Unicode codepoint, turning dmoji
+    // into a general character map on top of the existing dmenu + clipboard
+    // plumbing
+    if has_flag("--unicode", "-u") {
+        let unicode_data = data.load_file(UNICODE_DATA);
+
+        for entry in UnicodeScanner::new().entries(&unicode_data) {
+            if let Sequence::Literal(seq) = entry.sequence {
+                // skip the bare Fitzpatrick modifiers here too when
+                // collapsing skin-tone variants, so they don't reappear
+                // under their raw UnicodeData name (e.g. "emoji modifier
+                // fitzpatrick type-4-5") alongside the emoji picker's own
+                // handling of the same codepoints
+                if collapse_skin_tones && seq.chars().last().is_some_and(is_skin_tone_modifier) {
+                    continue;
+                }
+
+                map.insert(entry.description.to_lowercase(), seq);
+            }
+        }
+    }
+
     // spawn dmenu
     let menu_proc = Command::new("dmenu")
         .stdin(Stdio::piped())
@@ -170,10 +515,22 @@ fn main() {
         }
     };
 
-    // write emoji choices
+    // write emoji choices, frequently/recently used ones first
+    let mut history = History::load();
+    let mut entries: Vec<(&String, &String)> = map.iter().collect();
+
+    entries.sort_by(|(a_descr, a_seq), (b_descr, b_seq)| {
+        let a_score = history.score(a_seq);
+        let b_score = history.score(b_seq);
+
+        b_score.partial_cmp(&a_score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a_descr.cmp(b_descr))
+    });
+
     let mut dmenu_in = menu_proc.stdin.take().unwrap();
 
-    for (descr, _) in &map {
+    for (descr, _) in entries {
         let _ = write!(dmenu_in, "{}\n", descr);
     }
 
@@ -199,20 +556,234 @@ fn main() {
         }
     };
 
-    // spawn wl-copy
-    let wl_copy_proc = Command::new("wl-copy")
-        .stdin(Stdio::piped())
-        .spawn();
+    // bump this sequence's frecency so it ranks higher next time
+    history.record(emoji);
 
-    let mut wl_copy_proc = match wl_copy_proc {
-        Ok(prc) => prc,
+    // send the selected sequence out through the resolved output backend
+    output.send(emoji, primary);
+}
+
+// Look up a `--flag value` or `--flag=value` style CLI argument.
+fn arg_value(flag: &str) -> Option<String> {
+    let mut args = std::env::args().skip(1);
+    let prefix = format!("{}=", flag);
+
+    while let Some(arg) = args.next() {
+        if arg == flag {
+            return args.next();
+        } else if let Some(value) = arg.strip_prefix(&prefix) {
+            return Some(value.to_string());
+        }
+    }
+
+    None
+}
+
+// Check whether a bare CLI flag (exact token) was passed.
+fn has_any_flag(flag: &str) -> bool {
+    std::env::args().skip(1).any(|arg| arg == flag)
+}
+
+// Check whether a bare CLI flag (long or short form) was passed.
+fn has_flag(long: &str, short: &str) -> bool {
+    has_any_flag(long) || has_any_flag(short)
+}
+
+// The 5 Fitzpatrick skin-tone modifiers, U+1F3FB..U+1F3FF, in order.
+const FITZPATRICK_TONES: [&str; 5] = [
+    "light skin tone",
+    "medium-light skin tone",
+    "medium skin tone",
+    "medium-dark skin tone",
+    "dark skin tone",
+];
+
+fn is_skin_tone_modifier(ch: char) -> bool {
+    ('\u{1F3FB}'..='\u{1F3FF}').contains(&ch)
+}
+
+// Whether to collapse skin-tone variants down to the default (no-modifier)
+// glyph, via `--collapse-skin-tones`/`--expand-skin-tones` or
+// `DMOJI_SKIN_TONES=collapse`. Expanded (showing every variant) is the
+// default.
+fn collapse_skin_tones() -> bool {
+    if has_any_flag("--collapse-skin-tones") {
+        return true;
+    }
+
+    if has_any_flag("--expand-skin-tones") {
+        return false;
+    }
+
+    std::env::var("DMOJI_SKIN_TONES").map(|v| v == "collapse").unwrap_or(false)
+}
+
+// Derive a per-element label template from a range description like
+// "keycap: 0..keycap: 9", by finding the prefix its low/high endpoint
+// labels share (here "keycap: ").
+fn range_desc_template(description: &str) -> Option<String> {
+    let (low, high) = description.split_once("..")?;
+    let low = low.trim();
+    let high = high.trim();
+
+    let prefix_len = low.chars()
+        .zip(high.chars())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    if prefix_len == 0 {
+        None
+    } else {
+        Some(low.chars().take(prefix_len).collect())
+    }
+}
+
+// Resolve the locale to show names in: `--lang`/`--lang=<lang>` takes
+// precedence over the `DMOJI_LANG` environment variable, defaulting to "en".
+fn resolve_lang() -> String {
+    arg_value("--lang").unwrap_or_else(|| {
+        std::env::var("DMOJI_LANG").unwrap_or_else(|_| "en".to_string())
+    })
+}
+
+// Where a selected sequence ends up. `WlCopy`/`Xclip`/`Xsel` put it on the
+// clipboard (or the primary selection, with `--primary`); `Wtype`/`Xdotool`
+// type it directly into the focused window instead.
+#[derive(Clone, Copy)]
+enum Output {
+    WlCopy,
+    Xclip,
+    Xsel,
+    Wtype,
+    Xdotool,
+}
+
+impl Output {
+    // Resolve the backend from `--output`/`DMOJI_OUTPUT`, falling back to
+    // autodetection: prefer wl-copy under Wayland, else X11's xclip.
+    pub fn resolve() -> Self {
+        if let Some(name) = arg_value("--output") {
+            return Self::parse(&name);
+        }
+
+        if let Ok(name) = std::env::var("DMOJI_OUTPUT") {
+            return Self::parse(&name);
+        }
+
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            Output::WlCopy
+        } else {
+            Output::Xclip
+        }
+    }
+
+    fn parse(name: &str) -> Self {
+        match name {
+            "wl-copy" => Output::WlCopy,
+            "xclip" => Output::Xclip,
+            "xsel" => Output::Xsel,
+            "wtype" => Output::Wtype,
+            "xdotool" => Output::Xdotool,
+            other => {
+                eprintln!("dmoji: unknown output backend {:?}", other);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // Send `text` out through this backend. `primary` selects the primary
+    // selection instead of the clipboard; it has no effect on the auto-type
+    // backends.
+    pub fn send(self, text: &str, primary: bool) {
+        match self {
+            Output::WlCopy => {
+                let mut cmd = Command::new("wl-copy");
+
+                if primary {
+                    cmd.arg("--primary");
+                }
+
+                pipe_to(&mut cmd, "wl-copy", text);
+            }
+            Output::Xclip => {
+                let mut cmd = Command::new("xclip");
+                cmd.arg("-selection").arg(if primary { "primary" } else { "clipboard" });
+                pipe_to(&mut cmd, "xclip", text);
+            }
+            Output::Xsel => {
+                let mut cmd = Command::new("xsel");
+                cmd.arg(if primary { "--primary" } else { "--clipboard" }).arg("--input");
+                pipe_to(&mut cmd, "xsel", text);
+            }
+            Output::Wtype => {
+                let mut cmd = Command::new("wtype");
+                cmd.arg(text);
+                run(&mut cmd, "wtype");
+            }
+            Output::Xdotool => {
+                let mut cmd = Command::new("xdotool");
+                cmd.arg("type").arg("--").arg(text);
+                run(&mut cmd, "xdotool");
+            }
+        }
+    }
+}
+
+// Spawn `cmd`, printing a clear error (rather than a bare spawn failure) if
+// its helper binary isn't installed.
+fn spawn_checked(cmd: &mut Command, bin: &str) -> std::process::Child {
+    match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            eprintln!("dmoji: '{}' not found in PATH; install it to use this output mode", bin);
+            std::process::exit(1);
+        }
         Err(e) => {
-            eprintln!("dmoji: failed to spawn wl-copy: {:?}", e);
+            eprintln!("dmoji: failed to spawn {}: {:?}", bin, e);
             std::process::exit(1);
         }
-    };
+    }
+}
+
+// Spawn `cmd` with piped stdin and write `text` to it, for backends like
+// wl-copy/xclip/xsel that read the clipboard contents from stdin.
+fn pipe_to(cmd: &mut Command, bin: &str, text: &str) {
+    let mut child = spawn_checked(cmd.stdin(Stdio::piped()), bin);
+    let mut stdin = child.stdin.take().unwrap();
+    let _ = stdin.write_all(text.as_bytes());
+    drop(stdin);
+    let _ = child.wait();
+}
+
+// Spawn `cmd` and wait for it to finish, for backends like wtype/xdotool
+// that take the text to type as a CLI argument rather than on stdin.
+fn run(cmd: &mut Command, bin: &str) {
+    let mut child = spawn_checked(cmd, bin);
+    let _ = child.wait();
+}
 
-    // write emoji corresponding to selection to wl-copy
-    let mut wl_copy_in = wl_copy_proc.stdin.take().unwrap();
-    let _ = wl_copy_in.write_all(emoji.as_bytes());
+// Build the dmenu label for a sequence, appending any known search keywords
+// and :shortcode: aliases so the line stays searchable by more than just its
+// terse Unicode description, while still identifying exactly one sequence.
+fn search_label(
+    description: &str,
+    seq: &str,
+    keywords: &HashMap<String, Vec<String>>,
+    shortcodes: &HashMap<String, Vec<String>>,
+) -> String {
+    let mut terms: Vec<&str> = Vec::new();
+
+    if let Some(words) = keywords.get(seq) {
+        terms.extend(words.iter().map(String::as_str));
+    }
+
+    if let Some(aliases) = shortcodes.get(seq) {
+        terms.extend(aliases.iter().map(String::as_str));
+    }
+
+    if terms.is_empty() {
+        description.to_string()
+    } else {
+        format!("{} ({})", description, terms.join(", "))
+    }
 }